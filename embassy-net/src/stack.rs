@@ -0,0 +1,106 @@
+use core::cell::RefCell;
+use core::task::Waker;
+
+use critical_section::Mutex;
+use heapless::Vec;
+use smoltcp::iface::Interface;
+use smoltcp::time::Instant;
+use smoltcp::wire::{IpAddress, Ipv4Address};
+
+/// Upper bound on the number of multicast groups a single stack can be a member of at once.
+const MAX_MULTICAST_GROUPS: usize = 4;
+
+pub(crate) struct StackInner {
+    pub iface: Interface<'static>,
+    waker: Option<Waker>,
+    joined_groups: Vec<Ipv4Address, MAX_MULTICAST_GROUPS>,
+}
+
+static STACK: Mutex<RefCell<Option<StackInner>>> = Mutex::new(RefCell::new(None));
+
+pub struct Stack;
+
+impl Stack {
+    /// Runs `f` with exclusive access to the stack's interface and socket set.
+    ///
+    /// Panics if called before the stack has been initialized, or reentrantly.
+    pub(crate) fn with<R>(f: impl FnOnce(&mut StackInner) -> R) -> R {
+        critical_section::with(|cs| {
+            let mut inner = STACK.borrow(cs).borrow_mut();
+            let inner = inner.as_mut().expect("Stack not initialized");
+            f(inner)
+        })
+    }
+
+    /// Joins an IPv4 multicast group.
+    ///
+    /// Emits an IGMPv2 Membership Report for `addr` and programs the group into the interface's
+    /// accepted-address filter, so frames addressed to it start reaching sockets bound to it.
+    /// Subsequent IGMP Queries for the group are answered automatically out of the poll loop.
+    pub fn join_multicast_group(addr: Ipv4Address) -> Result<bool, MulticastError> {
+        Self::with(|stack| {
+            if stack.joined_groups.iter().any(|g| *g == addr) {
+                return Ok(false);
+            }
+            if stack.joined_groups.is_full() {
+                return Err(MulticastError::GroupTableFull);
+            }
+            let now = Instant::from_millis(embassy_time::Instant::now().as_millis() as i64);
+            let joined = stack
+                .iface
+                .join_multicast_group(IpAddress::Ipv4(addr), now)
+                .map_err(|_| MulticastError::Exhausted)?;
+            // Only record the group once smoltcp has actually joined it, so a failed call
+            // doesn't permanently block future retries via the `any(...)` short-circuit above.
+            stack
+                .joined_groups
+                .push(addr)
+                .expect("capacity already checked above");
+            stack.wake();
+            Ok(joined)
+        })
+    }
+
+    /// Leaves a previously-joined IPv4 multicast group, sending a Leave Group message to
+    /// 224.0.0.2.
+    pub fn leave_multicast_group(addr: Ipv4Address) -> Result<bool, MulticastError> {
+        Self::with(|stack| {
+            let Some(idx) = stack.joined_groups.iter().position(|g| *g == addr) else {
+                return Ok(false);
+            };
+            let now = Instant::from_millis(embassy_time::Instant::now().as_millis() as i64);
+            let left = stack
+                .iface
+                .leave_multicast_group(IpAddress::Ipv4(addr), now)
+                .map_err(|_| MulticastError::Exhausted)?;
+            // Only drop the group from the tracking table once smoltcp has actually left it, so
+            // a failed call doesn't leave us thinking we're no longer a member when we are.
+            stack.joined_groups.swap_remove(idx);
+            stack.wake();
+            Ok(left)
+        })
+    }
+}
+
+impl StackInner {
+    pub(crate) fn wake(&self) {
+        if let Some(waker) = &self.waker {
+            waker.wake_by_ref();
+        }
+    }
+
+    /// Polls the interface, driving DHCP, IGMP membership report/query handling, and socket
+    /// egress. IGMP reports scheduled in response to a Query are emitted here, as part of the
+    /// same `processed_any`/`emitted_any` readiness the rest of the stack relies on.
+    pub(crate) fn poll(&mut self, timestamp: Instant) -> bool {
+        self.iface.poll(timestamp)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MulticastError {
+    /// Already joined the maximum number of multicast groups this stack supports.
+    GroupTableFull,
+    /// The interface could not emit the Membership Report/Leave Group message.
+    Exhausted,
+}
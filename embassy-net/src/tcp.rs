@@ -0,0 +1,232 @@
+use core::marker::PhantomData;
+use core::mem;
+use core::task::Poll;
+use futures::future::poll_fn;
+use smoltcp::iface::{Context as SmolContext, SocketHandle};
+use smoltcp::socket::TcpSocket as SyncTcpSocket;
+use smoltcp::socket::TcpSocketBuffer;
+pub use smoltcp::socket::TcpState;
+use smoltcp::wire::IpEndpoint;
+
+use super::stack::Stack;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Error {
+    /// The remote end reset the connection.
+    ConnectionReset,
+}
+
+pub struct TcpSocket<'a> {
+    pub handle: SocketHandle,
+    ghost: PhantomData<&'a mut [u8]>,
+}
+
+impl<'a> TcpSocket<'a> {
+    pub fn new(rx_buffer: &'a mut [u8], tx_buffer: &'a mut [u8]) -> Self {
+        let handle = Stack::with(|stack| {
+            let rx_buffer: &'static mut [u8] = unsafe { mem::transmute(rx_buffer) };
+            let tx_buffer: &'static mut [u8] = unsafe { mem::transmute(tx_buffer) };
+
+            stack.iface.add_socket(SyncTcpSocket::new(
+                TcpSocketBuffer::new(rx_buffer),
+                TcpSocketBuffer::new(tx_buffer),
+            ))
+        });
+
+        Self {
+            handle,
+            ghost: PhantomData,
+        }
+    }
+
+    pub fn state(&self) -> TcpState {
+        with_socket(self.handle, |s, _| s.state())
+    }
+
+    pub fn may_send(&self) -> bool {
+        with_socket(self.handle, |s, _| s.may_send())
+    }
+
+    pub fn may_recv(&self) -> bool {
+        with_socket(self.handle, |s, _| s.may_recv())
+    }
+
+    pub fn can_send(&self) -> bool {
+        with_socket(self.handle, |s, _| s.can_send())
+    }
+
+    pub fn can_recv(&self) -> bool {
+        with_socket(self.handle, |s, _| s.can_recv())
+    }
+
+    pub fn local_endpoint(&self) -> IpEndpoint {
+        with_socket(self.handle, |s, _| s.local_endpoint())
+    }
+
+    pub fn remote_endpoint(&self) -> IpEndpoint {
+        with_socket(self.handle, |s, _| s.remote_endpoint())
+    }
+
+    pub fn set_timeout(&mut self, duration: Option<smoltcp::time::Duration>) {
+        with_socket(self.handle, |s, _| s.set_timeout(duration))
+    }
+
+    pub fn set_keep_alive(&mut self, interval: Option<smoltcp::time::Duration>) {
+        with_socket(self.handle, |s, _| s.set_keep_alive(interval))
+    }
+
+    /// Starts listening for an incoming connection on `port`.
+    pub fn listen(&mut self, port: u16) -> Result<(), smoltcp::Error> {
+        with_socket(self.handle, |s, _| s.listen(port))
+    }
+
+    /// Waits for a connection to be accepted after a call to `listen`.
+    pub async fn accept(&mut self) -> Result<(), Error> {
+        poll_fn(|cx| {
+            with_socket(self.handle, |s, _| match s.state() {
+                TcpState::Listen | TcpState::SynReceived => {
+                    s.register_recv_waker(cx.waker());
+                    Poll::Pending
+                }
+                TcpState::Closed => Poll::Ready(Err(Error::ConnectionReset)),
+                _ => Poll::Ready(Ok(())),
+            })
+        })
+        .await
+    }
+
+    /// Connects to `remote`, using `local_port` as the source port.
+    pub async fn connect<T>(&mut self, remote: T, local_port: u16) -> Result<(), Error>
+    where
+        T: Into<IpEndpoint>,
+    {
+        {
+            let remote = remote.into();
+            with_socket(self.handle, |s, cx| s.connect(cx, remote, local_port))
+                .map_err(|_| Error::ConnectionReset)?;
+        }
+        poll_fn(|cx| {
+            with_socket(self.handle, |s, _| match s.state() {
+                TcpState::SynSent | TcpState::SynReceived => {
+                    s.register_recv_waker(cx.waker());
+                    Poll::Pending
+                }
+                TcpState::Established => Poll::Ready(Ok(())),
+                _ => Poll::Ready(Err(Error::ConnectionReset)),
+            })
+        })
+        .await
+    }
+
+    pub async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Error> {
+        poll_fn(|cx| {
+            with_socket(self.handle, |s, _| {
+                if !s.may_recv() {
+                    return Poll::Ready(Ok(0));
+                }
+                if s.can_recv() {
+                    Poll::Ready(s.recv_slice(buf).map_err(|_| Error::ConnectionReset))
+                } else {
+                    s.register_recv_waker(cx.waker());
+                    Poll::Pending
+                }
+            })
+        })
+        .await
+    }
+
+    pub async fn write(&mut self, buf: &[u8]) -> Result<usize, Error> {
+        poll_fn(|cx| {
+            with_socket(self.handle, |s, _| {
+                if !s.may_send() {
+                    return Poll::Ready(Err(Error::ConnectionReset));
+                }
+                if s.can_send() {
+                    Poll::Ready(s.send_slice(buf).map_err(|_| Error::ConnectionReset))
+                } else {
+                    s.register_send_waker(cx.waker());
+                    Poll::Pending
+                }
+            })
+        })
+        .await
+    }
+
+    /// Waits for all outstanding data in the tx buffer to be acknowledged by the remote end.
+    pub async fn flush(&mut self) -> Result<(), Error> {
+        poll_fn(|cx| {
+            with_socket(self.handle, |s, _| {
+                if s.send_queue() == 0 {
+                    Poll::Ready(Ok(()))
+                } else if s.state() == TcpState::Closed {
+                    // The connection tore down (reset/abort) with data still queued: nothing
+                    // will ever drive the send queue to 0 or fire the waker again, so report
+                    // failure instead of hanging past teardown.
+                    Poll::Ready(Err(Error::ConnectionReset))
+                } else {
+                    s.register_send_waker(cx.waker());
+                    Poll::Pending
+                }
+            })
+        })
+        .await
+    }
+
+    /// Closes the write half, sending a FIN once buffered data has drained.
+    pub fn close(&mut self) {
+        with_socket(self.handle, |s, _| s.close())
+    }
+
+    /// Forcibly terminates the connection, sending a RST.
+    pub fn abort(&mut self) {
+        with_socket(self.handle, |s, _| s.abort())
+    }
+}
+
+impl embedded_io::Error for Error {
+    fn kind(&self) -> embedded_io::ErrorKind {
+        embedded_io::ErrorKind::ConnectionReset
+    }
+}
+
+impl<'a> embedded_io::Io for TcpSocket<'a> {
+    type Error = Error;
+}
+
+impl<'a> embedded_io::asynch::Read for TcpSocket<'a> {
+    async fn read(&mut self, buf: &mut [u8]) -> Result<usize, Self::Error> {
+        TcpSocket::read(self, buf).await
+    }
+}
+
+impl<'a> embedded_io::asynch::Write for TcpSocket<'a> {
+    async fn write(&mut self, buf: &[u8]) -> Result<usize, Self::Error> {
+        TcpSocket::write(self, buf).await
+    }
+
+    async fn flush(&mut self) -> Result<(), Self::Error> {
+        TcpSocket::flush(self).await
+    }
+}
+
+fn with_socket<R>(
+    handle: SocketHandle,
+    f: impl FnOnce(&mut SyncTcpSocket, &mut SmolContext) -> R,
+) -> R {
+    Stack::with(|stack| {
+        let res = {
+            let (s, cx) = stack.iface.get_socket_and_context::<SyncTcpSocket>(handle);
+            f(s, cx)
+        };
+        stack.wake();
+        res
+    })
+}
+
+impl<'a> Drop for TcpSocket<'a> {
+    fn drop(&mut self) {
+        Stack::with(|stack| {
+            stack.iface.remove_socket(self.handle);
+        })
+    }
+}
@@ -1,6 +1,8 @@
 use core::marker::PhantomData;
 use core::mem;
 use core::task::Poll;
+use embassy_futures::select::{select, Either};
+use embassy_time::Instant;
 use futures::future::poll_fn;
 use smoltcp::iface::{Context as SmolContext, SocketHandle};
 use smoltcp::socket::UdpPacketMetadata as SyncUdpPacketMetadata;
@@ -11,6 +13,7 @@ use smoltcp::wire::IpEndpoint;
 use super::stack::Stack;
 
 pub type UdpPacketMetadata = SyncUdpPacketMetadata;
+pub use smoltcp::socket::{PacketMeta, UdpMetadata};
 
 pub struct UdpSocket<'a> {
     pub handle: SocketHandle,
@@ -132,8 +135,195 @@ impl<'a> UdpSocket<'a> {
         })
         .await
     }
+
+    /// Waits until the tx ring has room for a payload of `size` bytes, reserves that slot and
+    /// hands it to `f` to fill in place, avoiding the extra copy `send_slice` incurs.
+    pub async fn send_with<R>(
+        &mut self,
+        size: usize,
+        endpoint: IpEndpoint,
+        f: impl FnOnce(&mut [u8]) -> R,
+    ) -> Result<R, smoltcp::Error> {
+        let mut f = Some(f);
+        poll_fn(|cx| {
+            with_socket(self.handle, |s, _| match s.send(size, endpoint) {
+                Ok(buf) => Poll::Ready(Ok(f.take().unwrap()(buf))),
+                Err(smoltcp::Error::Exhausted) => {
+                    s.register_send_waker(cx.waker());
+                    Poll::Pending
+                }
+                Err(err) => Poll::Ready(Err(err)),
+            })
+        })
+        .await
+    }
+
+    /// Waits for a packet and hands the borrowed payload (plus its source endpoint) to `f`
+    /// without copying it into a caller-provided buffer.
+    pub async fn recv_with<R>(&mut self, f: impl FnOnce(&[u8], IpEndpoint) -> R) -> R {
+        let mut f = Some(f);
+        poll_fn(|cx| {
+            with_socket(self.handle, |s, _| {
+                if s.can_recv() {
+                    match s.recv() {
+                        Ok((buf, ep)) => Poll::Ready(f.take().unwrap()(buf, ep)),
+                        Err(_) => {
+                            s.register_recv_waker(cx.waker());
+                            Poll::Pending
+                        }
+                    }
+                } else {
+                    s.register_recv_waker(cx.waker());
+                    Poll::Pending
+                }
+            })
+        })
+        .await
+    }
+
+    /// Inspects the head packet in the rx queue without dequeuing it.
+    ///
+    /// Returns `Err(smoltcp::Error::Exhausted)` if no packet is queued. Unlike `recv_slice`,
+    /// this does not register a waker; callers that want to wait should poll `can_recv` or use
+    /// `recv_with`/`recv_slice` instead.
+    pub fn peek(&mut self) -> Result<(&[u8], IpEndpoint), smoltcp::Error> {
+        with_socket(self.handle, |s, _| {
+            // SAFETY: smoltcp's `peek` borrows from the socket's internal rx buffer, whose
+            // lifetime is tied to `self` via the `UdpSocket` constructor's `mem::transmute`, not
+            // to the short-lived `with_socket` closure borrow.
+            let (buf, ep) = s.peek()?;
+            let buf: &[u8] = unsafe { mem::transmute(buf) };
+            Ok((buf, ep))
+        })
+    }
+
+    /// Copies the head packet in the rx queue into `buf` without dequeuing it.
+    pub fn peek_slice(&mut self, buf: &mut [u8]) -> Result<(usize, IpEndpoint), smoltcp::Error> {
+        with_socket(self.handle, |s, _| s.peek_slice(buf))
+    }
+
+    /// Like `recv_slice`, but also returns the packet's `UdpMetadata` (source endpoint plus,
+    /// under the `packetmeta-id` feature, the packet identifier smoltcp assigned it), so callers
+    /// can correlate a request with its eventual response or a phy-level trace.
+    pub async fn recv_slice_meta(&mut self, buf: &mut [u8]) -> (usize, UdpMetadata) {
+        poll_fn(|cx| {
+            with_socket(self.handle, |s, _| {
+                if s.can_recv() {
+                    match s.recv_slice_meta(buf) {
+                        Ok((n, meta)) => Poll::Ready((n, meta)),
+                        Err(_) => {
+                            s.register_recv_waker(cx.waker());
+                            Poll::Pending
+                        }
+                    }
+                } else {
+                    s.register_recv_waker(cx.waker());
+                    Poll::Pending
+                }
+            })
+        })
+        .await
+    }
+
+    /// Like `send_slice`, but lets the caller supply the full `UdpMetadata` (e.g. to echo back
+    /// the packet id of the datagram this is a response to) instead of a bare `IpEndpoint`.
+    pub async fn send_slice_meta(
+        &mut self,
+        buf: &[u8],
+        meta: UdpMetadata,
+    ) -> Result<(), smoltcp::Error> {
+        poll_fn(|cx| {
+            with_socket(self.handle, |s, _| match s.send_slice_meta(buf, meta) {
+                Ok(()) => Poll::Ready(Ok(())),
+                Err(smoltcp::Error::Exhausted) => {
+                    s.register_send_waker(cx.waker());
+                    Poll::Pending
+                }
+                Err(err) => Poll::Ready(Err(err)),
+            })
+        })
+        .await
+    }
+
+    /// Non-blocking `recv_slice`: returns `Err(WouldBlock)` immediately instead of registering a
+    /// waker, for use in poll-style loops that don't want to suspend the current task.
+    pub fn try_recv_slice(&mut self, buf: &mut [u8]) -> Result<(usize, IpEndpoint), WouldBlock> {
+        with_socket(self.handle, |s, _| {
+            if s.can_recv() {
+                s.recv_slice(buf).map_err(|_| WouldBlock)
+            } else {
+                Err(WouldBlock)
+            }
+        })
+    }
+
+    /// Non-blocking `send_slice`: returns `Err(TrySendError::WouldBlock)` immediately instead of
+    /// registering a waker when the tx ring is full, same as `send_slice` would await on. Any
+    /// other error (unaddressable endpoint, oversized payload, ...) is propagated as-is, since
+    /// retrying it would spin forever rather than eventually succeed.
+    pub fn try_send_slice(&mut self, buf: &[u8], endpoint: IpEndpoint) -> Result<(), TrySendError> {
+        with_socket(self.handle, |s, _| match s.send_slice(buf, endpoint) {
+            Ok(()) => Ok(()),
+            Err(smoltcp::Error::Exhausted) => Err(TrySendError::WouldBlock),
+            Err(err) => Err(TrySendError::Other(err)),
+        })
+    }
+
+    /// Like `recv_slice`, but gives up and returns `Err(TimedOut)` if no packet has arrived by
+    /// `deadline`. Needed for request/response protocols that must retransmit after an RTO
+    /// instead of awaiting forever. When the deadline wins, the dropped `recv_slice` future does
+    /// not clear the recv waker it registered: the socket keeps the calling task's waker until
+    /// the next recv waker registration replaces it.
+    pub async fn recv_slice_deadline(
+        &mut self,
+        buf: &mut [u8],
+        deadline: Instant,
+    ) -> Result<(usize, IpEndpoint), TimedOut> {
+        match select(self.recv_slice(buf), embassy_time::Timer::at(deadline)).await {
+            Either::First(result) => Ok(result),
+            Either::Second(_) => Err(TimedOut),
+        }
+    }
+
+    /// Like `send_slice`, but gives up and returns `Err(TimedOut)` if the send hasn't completed
+    /// by `deadline`.
+    pub async fn send_slice_deadline(
+        &mut self,
+        buf: &[u8],
+        endpoint: IpEndpoint,
+        deadline: Instant,
+    ) -> Result<Result<(), smoltcp::Error>, TimedOut> {
+        match select(
+            self.send_slice(buf, endpoint),
+            embassy_time::Timer::at(deadline),
+        )
+        .await
+        {
+            Either::First(result) => Ok(result),
+            Either::Second(_) => Err(TimedOut),
+        }
+    }
 }
 
+/// Returned by `try_recv_slice` when the operation could not complete without waiting
+/// (`try_send_slice` reports the same condition as `TrySendError::WouldBlock`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WouldBlock;
+
+/// Returned by `try_send_slice`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrySendError {
+    /// The tx ring is full; same condition `send_slice` would await on.
+    WouldBlock,
+    /// A non-transient error that retrying will not fix.
+    Other(smoltcp::Error),
+}
+
+/// Returned by `recv_slice_deadline`/`send_slice_deadline` when the deadline passed before the
+/// operation completed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimedOut;
+
 fn with_socket<R>(
     handle: SocketHandle,
     f: impl FnOnce(&mut SyncUdpSocket, &mut SmolContext) -> R,